@@ -0,0 +1,159 @@
+// Purchase-approval flow: when a watcher goes in-stock, the monitoring
+// loop asks the user before checking out. The outcome distinguishes a
+// request the user actively denied from one that was merely canceled
+// (timeout, window closed, or an internal error) so the loop can react
+// differently.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+use tokio::sync::oneshot;
+
+use crate::show_main_window;
+
+/// How long a request waits for the user before it's canceled.
+const APPROVAL_TIMEOUT: Duration = Duration::from_secs(120);
+
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Default)]
+pub struct ApprovalState {
+    pending: Mutex<HashMap<String, oneshot::Sender<ApprovalDecision>>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ApprovalRequest {
+    pub id: String,
+    pub watcher_id: String,
+    pub url: String,
+    pub expires_at: i64,
+}
+
+/// The user's explicit choice, sent back through `respond_to_request`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    Approved,
+    Denied,
+}
+
+/// What the monitoring loop actually sees. `Canceled` covers every path
+/// that isn't an explicit user decision.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalOutcome {
+    Approved,
+    Denied,
+    Canceled,
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Raises an approval request for `watcher_id`, focuses the window, and
+/// waits up to `APPROVAL_TIMEOUT` for the user's decision.
+pub async fn request_approval(app: &AppHandle, watcher_id: String, url: String) -> ApprovalOutcome {
+    let id = format!("req-{}", NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed));
+    let (tx, rx) = oneshot::channel();
+
+    {
+        let approvals = app.state::<ApprovalState>();
+        approvals.pending.lock().unwrap().insert(id.clone(), tx);
+    }
+
+    let request = ApprovalRequest {
+        id: id.clone(),
+        watcher_id,
+        url,
+        expires_at: now_unix() + APPROVAL_TIMEOUT.as_secs() as i64,
+    };
+
+    show_main_window(app);
+    let _ = app.emit_all("approval-request", &request);
+
+    let outcome = outcome_for_wait_result(tokio::time::timeout(APPROVAL_TIMEOUT, rx).await);
+
+    app.state::<ApprovalState>()
+        .pending
+        .lock()
+        .unwrap()
+        .remove(&id);
+
+    outcome
+}
+
+/// Maps the result of waiting on the decision channel to an outcome.
+/// Sender dropped without a decision or the wait timed out: neither is a
+/// user decision, so both are canceled. Closing the window does not drop
+/// the sender; such a request is canceled when the wait times out.
+fn outcome_for_wait_result(
+    result: Result<Result<ApprovalDecision, oneshot::error::RecvError>, tokio::time::error::Elapsed>,
+) -> ApprovalOutcome {
+    match result {
+        Ok(Ok(ApprovalDecision::Approved)) => ApprovalOutcome::Approved,
+        Ok(Ok(ApprovalDecision::Denied)) => ApprovalOutcome::Denied,
+        Ok(Err(_)) | Err(_) => ApprovalOutcome::Canceled,
+    }
+}
+
+#[tauri::command]
+pub async fn respond_to_request(
+    id: String,
+    decision: ApprovalDecision,
+    approvals: State<'_, ApprovalState>,
+) -> Result<(), String> {
+    let sender = approvals
+        .pending
+        .lock()
+        .unwrap()
+        .remove(&id)
+        .ok_or_else(|| format!("no pending approval request with id \"{}\"", id))?;
+
+    sender
+        .send(decision)
+        .map_err(|_| "approval request already timed out".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn approved_decision_maps_to_approved() {
+        let (tx, rx) = oneshot::channel();
+        tx.send(ApprovalDecision::Approved).unwrap();
+        let result = tokio::time::timeout(Duration::from_secs(1), rx).await;
+        assert_eq!(outcome_for_wait_result(result), ApprovalOutcome::Approved);
+    }
+
+    #[tokio::test]
+    async fn denied_decision_maps_to_denied() {
+        let (tx, rx) = oneshot::channel();
+        tx.send(ApprovalDecision::Denied).unwrap();
+        let result = tokio::time::timeout(Duration::from_secs(1), rx).await;
+        assert_eq!(outcome_for_wait_result(result), ApprovalOutcome::Denied);
+    }
+
+    #[tokio::test]
+    async fn dropped_sender_maps_to_canceled() {
+        let (tx, rx) = oneshot::channel::<ApprovalDecision>();
+        drop(tx);
+        let result = tokio::time::timeout(Duration::from_secs(1), rx).await;
+        assert_eq!(outcome_for_wait_result(result), ApprovalOutcome::Canceled);
+    }
+
+    #[tokio::test]
+    async fn timing_out_maps_to_canceled() {
+        let (_tx, rx) = oneshot::channel::<ApprovalDecision>();
+        let result = tokio::time::timeout(Duration::from_millis(10), rx).await;
+        assert_eq!(outcome_for_wait_result(result), ApprovalOutcome::Canceled);
+    }
+}
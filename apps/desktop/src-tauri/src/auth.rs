@@ -0,0 +1,215 @@
+// Token lifecycle: stores the access/refresh token pair and an absolute
+// expiry alongside it, and gives the rest of the app a single place to
+// route authenticated requests through so they transparently refresh.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use keyring::Entry;
+use tauri::{AppHandle, Manager};
+
+use crate::{LoginResponse, API_URL, SERVICE_NAME};
+
+/// Refresh proactively once the access token is within this many seconds
+/// of expiring, rather than waiting for a 401.
+const REFRESH_THRESHOLD_SECS: i64 = 60;
+
+fn entry(key: &str) -> Result<Entry, String> {
+    Entry::new(SERVICE_NAME, key).map_err(|e| e.to_string())
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+pub async fn store_tokens(access_token: &str, refresh_token: &str, expires_in: i64) -> Result<(), String> {
+    crate::store_token(access_token.to_string()).await?;
+    entry("refresh_token")?
+        .set_password(refresh_token)
+        .map_err(|e| e.to_string())?;
+    entry("token_expires_at")?
+        .set_password(&(now_unix() + expires_in).to_string())
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub fn clear_tokens() -> Result<(), String> {
+    for key in ["access_token", "refresh_token", "token_expires_at"] {
+        match entry(key)?.delete_password() {
+            Ok(_) | Err(keyring::Error::NoEntry) => {}
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+    Ok(())
+}
+
+fn get_refresh_token() -> Result<Option<String>, String> {
+    match entry("refresh_token")?.get_password() {
+        Ok(token) => Ok(Some(token)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn get_expires_at() -> Result<Option<i64>, String> {
+    match entry("token_expires_at")?.get_password() {
+        Ok(raw) => Ok(raw.parse().ok()),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn is_near_expiry(expires_at: Option<i64>, now: i64) -> bool {
+    matches!(expires_at, Some(at) if at - now <= REFRESH_THRESHOLD_SECS)
+}
+
+/// Why a refresh was attempted, which decides what happens on failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RefreshTrigger {
+    /// The access token is merely close to `expires_in`; it may still be
+    /// good. A failed refresh here (e.g. a network blip) shouldn't throw
+    /// away a session that's still valid.
+    Proactive,
+    /// A request actually came back 401, so the access token is confirmed
+    /// invalid. A failed refresh here means the session really is over.
+    Reactive,
+}
+
+fn clears_session_on_failure(trigger: RefreshTrigger) -> bool {
+    matches!(trigger, RefreshTrigger::Reactive)
+}
+
+/// Prefixed onto the error returned when a refresh failure clears the
+/// session, so callers that need to react differently (the monitoring
+/// loop, in particular) can tell it apart from a transient failure
+/// without re-deriving the same trigger logic.
+const SESSION_EXPIRED_PREFIX: &str = "session expired:";
+
+/// Whether `err` came from a refresh failure that cleared the session, as
+/// opposed to a transient network/HTTP error that's worth retrying.
+pub fn is_session_expired(err: &str) -> bool {
+    err.starts_with(SESSION_EXPIRED_PREFIX)
+}
+
+/// Returns a valid access token, refreshing first if it's close to
+/// expiring. If that proactive refresh fails, falls back to the existing
+/// token rather than clearing the session - a confirmed-bad token will
+/// still be caught by the 401 retry in `authenticated_request`.
+async fn ensure_fresh_token(app: &AppHandle) -> Result<String, String> {
+    let token = crate::get_token().await?.ok_or("No token found")?;
+    if !is_near_expiry(get_expires_at()?, now_unix()) {
+        return Ok(token);
+    }
+    match attempt_refresh(app, RefreshTrigger::Proactive).await {
+        Ok(fresh) => Ok(fresh),
+        Err(_) => Ok(token),
+    }
+}
+
+/// POSTs the stored refresh token to `/auth/refresh` and rotates both
+/// stored values. Used for the 401 retry path, where failure means the
+/// session is genuinely over: tokens are cleared and a `session-expired`
+/// event is emitted so the UI can prompt re-login.
+pub async fn refresh_access_token(app: &AppHandle) -> Result<String, String> {
+    attempt_refresh(app, RefreshTrigger::Reactive).await
+}
+
+async fn attempt_refresh(app: &AppHandle, trigger: RefreshTrigger) -> Result<String, String> {
+    match do_refresh().await {
+        Err(err) if clears_session_on_failure(trigger) => {
+            let _ = clear_tokens();
+            let _ = app.emit_all("session-expired", serde_json::json!({ "reason": &err }));
+            Err(format!("{} {}", SESSION_EXPIRED_PREFIX, err))
+        }
+        Err(err) => {
+            eprintln!("auth: proactive token refresh failed, keeping existing token: {}", err);
+            Err(err)
+        }
+        Ok(token) => Ok(token),
+    }
+}
+
+async fn do_refresh() -> Result<String, String> {
+    let refresh_token = get_refresh_token()?.ok_or("No refresh token found")?;
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/auth/refresh", API_URL))
+        .json(&serde_json::json!({ "refreshToken": refresh_token }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.status().is_success() {
+        return Err("Token refresh failed".to_string());
+    }
+
+    let body: LoginResponse = response.json().await.map_err(|e| e.to_string())?;
+    store_tokens(&body.access_token, &body.refresh_token, body.expires_in).await?;
+    Ok(body.access_token)
+}
+
+/// Sends an authenticated request built from the current access token,
+/// refreshing and retrying once if the server responds with a 401.
+pub async fn authenticated_request<F>(app: &AppHandle, build: F) -> Result<reqwest::Response, String>
+where
+    F: Fn(&str) -> reqwest::RequestBuilder,
+{
+    let token = ensure_fresh_token(app).await?;
+    let response = build(&token).send().await.map_err(|e| e.to_string())?;
+
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        let token = refresh_access_token(app).await?;
+        return build(&token).send().await.map_err(|e| e.to_string());
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // These cover the pure decision logic only. The keychain storage and
+    // `/auth/refresh` call aren't unit-tested here: this sandbox has no
+    // secret-service/dbus provider for `keyring` to talk to, so a real
+    // store/fetch round-trip isn't something a plain `cargo test` can
+    // exercise in this environment.
+
+    #[test]
+    fn is_near_expiry_true_at_threshold() {
+        assert!(is_near_expiry(Some(1_000), 1_000 - REFRESH_THRESHOLD_SECS));
+    }
+
+    #[test]
+    fn is_near_expiry_false_with_margin() {
+        assert!(!is_near_expiry(Some(10_000), 0));
+    }
+
+    #[test]
+    fn is_near_expiry_false_without_a_stored_expiry() {
+        assert!(!is_near_expiry(None, 0));
+    }
+
+    #[test]
+    fn proactive_refresh_failure_does_not_clear_the_session() {
+        assert!(!clears_session_on_failure(RefreshTrigger::Proactive));
+    }
+
+    #[test]
+    fn reactive_refresh_failure_clears_the_session() {
+        assert!(clears_session_on_failure(RefreshTrigger::Reactive));
+    }
+
+    #[test]
+    fn is_session_expired_recognizes_the_prefixed_error() {
+        assert!(is_session_expired(&format!("{} refresh token invalid", SESSION_EXPIRED_PREFIX)));
+    }
+
+    #[test]
+    fn is_session_expired_false_for_a_transient_error() {
+        assert!(!is_session_expired("error sending request"));
+    }
+}
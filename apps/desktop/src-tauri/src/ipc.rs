@@ -0,0 +1,117 @@
+// Local IPC server: lets the `shopping-cli` companion binary drive a
+// running instance of the app over a Unix domain socket (or named pipe on
+// Windows) without going through the UI.
+
+use shopping_ipc::{IpcRequest, IpcResponse, WatcherDto};
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::{get_watchers, start_monitoring_internal, stop_monitoring_internal, AppState};
+
+pub fn start(app: AppHandle) {
+    // Called from `.setup()`, which runs on the main thread outside any
+    // Tokio runtime context - `tauri::async_runtime::spawn` hands this off
+    // to the runtime Tauri manages instead of panicking.
+    tauri::async_runtime::spawn(async move {
+        if let Err(err) = serve(app).await {
+            eprintln!("ipc: server exited: {}", err);
+        }
+    });
+}
+
+#[cfg(unix)]
+async fn serve(app: AppHandle) -> std::io::Result<()> {
+    use tokio::net::UnixListener;
+
+    let path = shopping_ipc::socket_path();
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path)?;
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let app = app.clone();
+        tokio::spawn(async move {
+            let _ = handle_connection(stream, app).await;
+        });
+    }
+}
+
+#[cfg(windows)]
+async fn serve(app: AppHandle) -> std::io::Result<()> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let pipe_name = shopping_ipc::pipe_name();
+    loop {
+        let server = ServerOptions::new().create(&pipe_name)?;
+        server.connect().await?;
+        let app = app.clone();
+        tokio::spawn(async move {
+            let _ = handle_connection(server, app).await;
+        });
+    }
+}
+
+async fn handle_connection<S>(stream: S, app: AppHandle) -> std::io::Result<()>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let (read_half, mut write_half) = tokio::io::split(stream);
+    let mut lines = BufReader::new(read_half).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<IpcRequest>(&line) {
+            Ok(request) => dispatch(request, &app).await,
+            Err(err) => IpcResponse::Error {
+                message: format!("invalid request: {}", err),
+            },
+        };
+
+        let mut payload = serde_json::to_string(&response).unwrap_or_else(|e| {
+            serde_json::to_string(&IpcResponse::Error {
+                message: e.to_string(),
+            })
+            .unwrap()
+        });
+        payload.push('\n');
+        write_half.write_all(payload.as_bytes()).await?;
+    }
+
+    Ok(())
+}
+
+async fn dispatch(request: IpcRequest, app: &AppHandle) -> IpcResponse {
+    let state = app.state::<AppState>();
+
+    match request {
+        IpcRequest::StartMonitoring => {
+            start_monitoring_internal(app.clone(), &state);
+            IpcResponse::Ok
+        }
+        IpcRequest::StopMonitoring => {
+            stop_monitoring_internal(&state);
+            IpcResponse::Ok
+        }
+        IpcRequest::IsMonitoring => IpcResponse::Monitoring {
+            active: *state.is_monitoring.lock().unwrap(),
+        },
+        IpcRequest::GetWatchers => match get_watchers(app.clone()).await {
+            Ok(watchers) => IpcResponse::Watchers {
+                items: watchers
+                    .into_iter()
+                    .map(|w| WatcherDto {
+                        id: w.id,
+                        name: w.name,
+                        url: w.url,
+                        status: w.status,
+                        last_check_at: w.last_check_at,
+                    })
+                    .collect(),
+            },
+            Err(message) => IpcResponse::Error { message },
+        },
+    }
+}
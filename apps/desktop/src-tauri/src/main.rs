@@ -6,12 +6,23 @@ use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
 use tauri::{Manager, State, SystemTray, SystemTrayEvent, SystemTrayMenu, CustomMenuItem};
 
+mod approval;
+mod auth;
+mod ipc;
+mod monitoring;
+mod shortcuts;
+
+use approval::ApprovalState;
+
+use monitoring::MonitoringHandle;
+
 const SERVICE_NAME: &str = "shopping-assistant";
 const API_URL: &str = "http://localhost:3001/api";
 
 #[derive(Default)]
 struct AppState {
     is_monitoring: Mutex<bool>,
+    monitoring_handle: Mutex<Option<MonitoringHandle>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -49,7 +60,7 @@ struct VerifyResponse {
     subscription: Subscription,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct Watcher {
     id: String,
     name: String,
@@ -88,13 +99,7 @@ async fn get_token() -> Result<Option<String>, String> {
 // Clear token from keychain
 #[tauri::command]
 async fn clear_token() -> Result<(), String> {
-    let entry = Entry::new(SERVICE_NAME, "access_token")
-        .map_err(|e| e.to_string())?;
-    match entry.delete_password() {
-        Ok(_) => Ok(()),
-        Err(keyring::Error::NoEntry) => Ok(()),
-        Err(e) => Err(e.to_string()),
-    }
+    auth::clear_tokens()
 }
 
 // Login to API
@@ -116,19 +121,25 @@ async fn login(email: String, password: String) -> Result<LoginResponse, String>
         return Err(format!("Login failed: {}", error_text));
     }
 
-    response.json::<LoginResponse>().await.map_err(|e| e.to_string())
+    let login_response = response.json::<LoginResponse>().await.map_err(|e| e.to_string())?;
+    auth::store_tokens(
+        &login_response.access_token,
+        &login_response.refresh_token,
+        login_response.expires_in,
+    )
+    .await?;
+    Ok(login_response)
 }
 
 // Verify token with server
 #[tauri::command]
-async fn verify_token(token: String) -> Result<VerifyResponse, String> {
-    let client = reqwest::Client::new();
-    let response = client
-        .get(format!("{}/auth/verify", API_URL))
-        .header("Authorization", format!("Bearer {}", token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+async fn verify_token(app: tauri::AppHandle) -> Result<VerifyResponse, String> {
+    let response = auth::authenticated_request(&app, |token| {
+        reqwest::Client::new()
+            .get(format!("{}/auth/verify", API_URL))
+            .header("Authorization", format!("Bearer {}", token))
+    })
+    .await?;
 
     if !response.status().is_success() {
         return Err("Token verification failed".to_string());
@@ -137,18 +148,22 @@ async fn verify_token(token: String) -> Result<VerifyResponse, String> {
     response.json::<VerifyResponse>().await.map_err(|e| e.to_string())
 }
 
+// Force-refresh the access token using the stored refresh token
+#[tauri::command]
+async fn refresh_token(app: tauri::AppHandle) -> Result<(), String> {
+    auth::refresh_access_token(&app).await?;
+    Ok(())
+}
+
 // Get user's watchers
 #[tauri::command]
-async fn get_watchers() -> Result<Vec<Watcher>, String> {
-    let token = get_token().await?.ok_or("No token found")?;
-    
-    let client = reqwest::Client::new();
-    let response = client
-        .get(format!("{}/watchers", API_URL))
-        .header("Authorization", format!("Bearer {}", token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+async fn get_watchers(app: tauri::AppHandle) -> Result<Vec<Watcher>, String> {
+    let response = auth::authenticated_request(&app, |token| {
+        reqwest::Client::new()
+            .get(format!("{}/watchers", API_URL))
+            .header("Authorization", format!("Bearer {}", token))
+    })
+    .await?;
 
     if !response.status().is_success() {
         return Err("Failed to fetch watchers".to_string());
@@ -160,18 +175,15 @@ async fn get_watchers() -> Result<Vec<Watcher>, String> {
 
 // Start monitoring
 #[tauri::command]
-async fn start_monitoring(state: State<'_, AppState>) -> Result<(), String> {
-    let mut is_monitoring = state.is_monitoring.lock().unwrap();
-    *is_monitoring = true;
-    // TODO: Implement actual monitoring loop
+async fn start_monitoring(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    start_monitoring_internal(app, &state);
     Ok(())
 }
 
 // Stop monitoring
 #[tauri::command]
 async fn stop_monitoring(state: State<'_, AppState>) -> Result<(), String> {
-    let mut is_monitoring = state.is_monitoring.lock().unwrap();
-    *is_monitoring = false;
+    stop_monitoring_internal(&state);
     Ok(())
 }
 
@@ -182,6 +194,33 @@ async fn is_monitoring(state: State<'_, AppState>) -> Result<bool, String> {
     Ok(*is_monitoring)
 }
 
+/// Shared with the IPC server and the global-shortcut handler so every
+/// entry point into "start monitoring" goes through the same code path.
+pub(crate) fn start_monitoring_internal(app: tauri::AppHandle, state: &AppState) {
+    let mut handle = state.monitoring_handle.lock().unwrap();
+    if handle.is_some() {
+        return;
+    }
+    *handle = Some(monitoring::spawn(app));
+    *state.is_monitoring.lock().unwrap() = true;
+}
+
+pub(crate) fn stop_monitoring_internal(state: &AppState) {
+    if let Some(handle) = state.monitoring_handle.lock().unwrap().take() {
+        handle.cancel();
+    }
+    *state.is_monitoring.lock().unwrap() = false;
+}
+
+/// Shared with the global-shortcut handler so it matches the tray's
+/// `LeftClick`/"show" behavior exactly.
+pub(crate) fn show_main_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
 fn main() {
     // Create system tray menu
     let quit = CustomMenuItem::new("quit".to_string(), "Quit");
@@ -193,12 +232,18 @@ fn main() {
 
     tauri::Builder::default()
         .manage(AppState::default())
+        .manage(ApprovalState::default())
+        .setup(|app| {
+            ipc::start(app.handle());
+            if let Err(err) = shortcuts::register_hotkeys(&app.handle()) {
+                eprintln!("shortcuts: failed to register hotkeys: {}", err);
+            }
+            Ok(())
+        })
         .system_tray(system_tray)
         .on_system_tray_event(|app, event| match event {
             SystemTrayEvent::LeftClick { .. } => {
-                let window = app.get_window("main").unwrap();
-                window.show().unwrap();
-                window.set_focus().unwrap();
+                show_main_window(app);
             }
             SystemTrayEvent::MenuItemClick { id, .. } => {
                 match id.as_str() {
@@ -206,9 +251,7 @@ fn main() {
                         std::process::exit(0);
                     }
                     "show" => {
-                        let window = app.get_window("main").unwrap();
-                        window.show().unwrap();
-                        window.set_focus().unwrap();
+                        show_main_window(app);
                     }
                     _ => {}
                 }
@@ -221,10 +264,17 @@ fn main() {
             clear_token,
             login,
             verify_token,
+            refresh_token,
             get_watchers,
             start_monitoring,
             stop_monitoring,
             is_monitoring,
+            shortcuts::get_hotkeys,
+            shortcuts::set_hotkeys,
+            approval::respond_to_request,
+            monitoring::get_poll_interval_config,
+            monitoring::set_default_poll_interval,
+            monitoring::set_watcher_poll_interval,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,331 @@
+// Background monitoring loop: polls the user's watchers on an interval and
+// surfaces status transitions to the frontend and the OS.
+
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri::api::notification::Notification;
+use tokio::sync::Notify;
+
+use crate::approval::{self, ApprovalOutcome};
+use crate::{get_watchers, stop_monitoring_internal, AppState, Watcher, SERVICE_NAME};
+
+/// Default interval between checks for a watcher with no override.
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 30;
+/// Floor on any configured interval so a typo can't hammer the API.
+const MIN_POLL_INTERVAL_SECS: u64 = 5;
+/// Backoff applied after a failed poll, doubled up to `MAX_BACKOFF` on each
+/// consecutive failure so a flaky connection doesn't hammer the API.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Poll interval configuration, persisted to `monitoring.json` in the app
+/// config dir. `overrides` lets a watcher be checked more or less often
+/// than `default_secs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PollIntervalConfig {
+    pub default_secs: u64,
+    #[serde(default)]
+    pub overrides: HashMap<String, u64>,
+}
+
+impl Default for PollIntervalConfig {
+    fn default() -> Self {
+        Self {
+            default_secs: DEFAULT_POLL_INTERVAL_SECS,
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+fn config_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app
+        .path_resolver()
+        .app_config_dir()
+        .ok_or("could not resolve app config directory")?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join("monitoring.json"))
+}
+
+fn load_interval_config(app: &AppHandle) -> PollIntervalConfig {
+    config_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_interval_config(app: &AppHandle, config: &PollIntervalConfig) -> Result<(), String> {
+    let path = config_path(app)?;
+    let raw = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+    fs::write(path, raw).map_err(|e| e.to_string())
+}
+
+fn interval_for(config: &PollIntervalConfig, watcher_id: &str) -> Duration {
+    let secs = config
+        .overrides
+        .get(watcher_id)
+        .copied()
+        .unwrap_or(config.default_secs);
+    Duration::from_secs(secs.max(MIN_POLL_INTERVAL_SECS))
+}
+
+/// The loop wakes at the fastest configured interval (global default or
+/// any override). A watcher with a longer interval is checked on the first
+/// wake-up at or after it falls due, which can be later than its interval.
+fn tick_interval(config: &PollIntervalConfig) -> Duration {
+    let fastest = config
+        .overrides
+        .values()
+        .copied()
+        .chain(std::iter::once(config.default_secs))
+        .min()
+        .unwrap_or(DEFAULT_POLL_INTERVAL_SECS);
+    Duration::from_secs(fastest.max(MIN_POLL_INTERVAL_SECS))
+}
+
+#[tauri::command]
+pub async fn get_poll_interval_config(app: AppHandle) -> Result<PollIntervalConfig, String> {
+    Ok(load_interval_config(&app))
+}
+
+#[tauri::command]
+pub async fn set_default_poll_interval(seconds: u64, app: AppHandle) -> Result<(), String> {
+    let mut config = load_interval_config(&app);
+    config.default_secs = seconds;
+    save_interval_config(&app, &config)
+}
+
+#[tauri::command]
+pub async fn set_watcher_poll_interval(
+    watcher_id: String,
+    seconds: Option<u64>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let mut config = load_interval_config(&app);
+    match seconds {
+        Some(secs) => {
+            config.overrides.insert(watcher_id, secs);
+        }
+        None => {
+            config.overrides.remove(&watcher_id);
+        }
+    }
+    save_interval_config(&app, &config)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WatcherUpdate {
+    watcher: Watcher,
+    previous_status: Option<String>,
+}
+
+/// Cancellation handle for the running monitoring task, stored in `AppState`
+/// so `stop_monitoring` can abort the loop without killing the process.
+pub struct MonitoringHandle {
+    cancel: Arc<Notify>,
+}
+
+impl MonitoringHandle {
+    pub fn cancel(&self) {
+        self.cancel.notify_one();
+    }
+}
+
+/// Spawns the polling loop and returns a handle used to cancel it. Uses
+/// `tauri::async_runtime::spawn` rather than `tokio::spawn` directly
+/// because callers include the global-shortcut callback, which runs on
+/// the shortcut-manager thread outside any Tokio runtime context.
+pub fn spawn(app: AppHandle) -> MonitoringHandle {
+    let cancel = Arc::new(Notify::new());
+    let task_cancel = cancel.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let mut last_status: HashMap<String, String> = HashMap::new();
+        // Outcome of the last approval request raised for a watcher while
+        // it's in stock. `Approved`/`Denied` are terminal until the watcher
+        // leaves stock again; `Canceled` is retried on the next poll.
+        let mut approval_state: HashMap<String, ApprovalOutcome> = HashMap::new();
+        // Next time each watcher is actually due to be checked, so a
+        // watcher with a longer configured interval isn't re-processed
+        // every time the faster tick wakes the loop.
+        let mut next_due: HashMap<String, Instant> = HashMap::new();
+        let mut backoff = INITIAL_BACKOFF;
+
+        'poll: loop {
+            tokio::select! {
+                _ = task_cancel.notified() => {
+                    break;
+                }
+                result = get_watchers(app.clone()) => {
+                    match result {
+                        Ok(watchers) => {
+                            backoff = INITIAL_BACKOFF;
+                            let interval_config = load_interval_config(&app);
+                            let now = Instant::now();
+
+                            for watcher in watchers {
+                                let due = next_due.get(&watcher.id).map(|at| now >= *at).unwrap_or(true);
+                                if !due {
+                                    continue;
+                                }
+                                next_due.insert(watcher.id.clone(), now + interval_for(&interval_config, &watcher.id));
+
+                                let previous = last_status.get(&watcher.id).cloned();
+                                if previous.as_deref() != Some(watcher.status.as_str()) {
+                                    notify_transition(&app, &watcher, previous.as_deref());
+                                    let _ = app.emit_all(
+                                        "watcher-update",
+                                        WatcherUpdate {
+                                            watcher: watcher.clone(),
+                                            previous_status: previous.clone(),
+                                        },
+                                    );
+                                }
+                                last_status.insert(watcher.id.clone(), watcher.status.clone());
+
+                                if watcher.status.eq_ignore_ascii_case("in_stock") {
+                                    let resolved = matches!(
+                                        approval_state.get(&watcher.id),
+                                        Some(ApprovalOutcome::Approved) | Some(ApprovalOutcome::Denied)
+                                    );
+                                    if !resolved {
+                                        // Approvals are awaited one at a time; this keeps the
+                                        // loop simple at the cost of serializing checkouts. The
+                                        // wait is raced against cancellation so `stop_monitoring`
+                                        // (and the panic-stop hotkey) can halt immediately instead
+                                        // of waiting out the full approval timeout.
+                                        let outcome = tokio::select! {
+                                            _ = task_cancel.notified() => break 'poll,
+                                            outcome = approval::request_approval(
+                                                &app,
+                                                watcher.id.clone(),
+                                                watcher.url.clone(),
+                                            ) => outcome,
+                                        };
+                                        log_approval_outcome(&watcher, outcome);
+                                        approval_state.insert(watcher.id.clone(), outcome);
+                                    }
+                                } else {
+                                    approval_state.remove(&watcher.id);
+                                }
+                            }
+
+                            tokio::select! {
+                                _ = task_cancel.notified() => break,
+                                _ = tokio::time::sleep(tick_interval(&interval_config)) => {}
+                            }
+                        }
+                        Err(err) if crate::auth::is_session_expired(&err) => {
+                            // The refresh token is confirmed dead and the session has
+                            // already been cleared (see `auth::attempt_refresh`). Retrying
+                            // would just hammer `/auth/refresh` with a token that's gone,
+                            // so stop outright rather than backing off - the user has to
+                            // log in again before monitoring can do anything useful.
+                            eprintln!("monitoring: session expired, stopping until re-login: {}", err);
+                            stop_monitoring_internal(&app.state::<AppState>());
+                            break;
+                        }
+                        Err(err) => {
+                            eprintln!("monitoring: poll failed, backing off {:?}: {}", backoff, err);
+                            tokio::select! {
+                                _ = task_cancel.notified() => break,
+                                _ = tokio::time::sleep(backoff) => {}
+                            }
+                            backoff = std::cmp::min(backoff * 2, MAX_BACKOFF);
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    MonitoringHandle { cancel }
+}
+
+fn log_approval_outcome(watcher: &Watcher, outcome: ApprovalOutcome) {
+    match outcome {
+        ApprovalOutcome::Approved => {
+            println!("monitoring: checkout approved for \"{}\"", watcher.name);
+            // TODO: hand off to the checkout flow once it exists.
+        }
+        ApprovalOutcome::Denied => {
+            println!("monitoring: checkout denied for \"{}\", skipping while in stock", watcher.name);
+        }
+        ApprovalOutcome::Canceled => {
+            println!("monitoring: approval canceled for \"{}\", will retry", watcher.name);
+        }
+    }
+}
+
+fn notify_transition(app: &AppHandle, watcher: &Watcher, previous: Option<&str>) {
+    let became_in_stock = previous
+        .map(|p| p.eq_ignore_ascii_case("out_of_stock") || p.eq_ignore_ascii_case("out-of-stock"))
+        .unwrap_or(false)
+        && watcher.status.eq_ignore_ascii_case("in_stock");
+
+    if !became_in_stock {
+        return;
+    }
+
+    let _ = Notification::new(SERVICE_NAME)
+        .title("Back in stock")
+        .body(format!("{} is now in stock", watcher.name))
+        .show();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(default_secs: u64, overrides: &[(&str, u64)]) -> PollIntervalConfig {
+        PollIntervalConfig {
+            default_secs,
+            overrides: overrides.iter().map(|(id, secs)| (id.to_string(), *secs)).collect(),
+        }
+    }
+
+    #[test]
+    fn interval_for_uses_the_default_with_no_override() {
+        let config = config(45, &[]);
+        assert_eq!(interval_for(&config, "watcher-1"), Duration::from_secs(45));
+    }
+
+    #[test]
+    fn interval_for_uses_a_watchers_override() {
+        let config = config(45, &[("watcher-1", 10)]);
+        assert_eq!(interval_for(&config, "watcher-1"), Duration::from_secs(10));
+        assert_eq!(interval_for(&config, "watcher-2"), Duration::from_secs(45));
+    }
+
+    #[test]
+    fn interval_for_floors_at_the_minimum() {
+        let config = config(45, &[("watcher-1", 1)]);
+        assert_eq!(
+            interval_for(&config, "watcher-1"),
+            Duration::from_secs(MIN_POLL_INTERVAL_SECS)
+        );
+    }
+
+    #[test]
+    fn tick_interval_is_the_default_with_no_overrides() {
+        let config = config(45, &[]);
+        assert_eq!(tick_interval(&config), Duration::from_secs(45));
+    }
+
+    #[test]
+    fn tick_interval_is_the_fastest_of_the_default_and_any_override() {
+        let config = config(45, &[("watcher-1", 60), ("watcher-2", 20)]);
+        assert_eq!(tick_interval(&config), Duration::from_secs(20));
+    }
+
+    #[test]
+    fn tick_interval_floors_at_the_minimum() {
+        let config = config(45, &[("watcher-1", 1)]);
+        assert_eq!(tick_interval(&config), Duration::from_secs(MIN_POLL_INTERVAL_SECS));
+    }
+}
@@ -0,0 +1,113 @@
+// Global hotkeys: bind chords to monitoring controls so they work even
+// when the window isn't focused. Chords are persisted to a config file and
+// re-registered whenever they change.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use tauri::{AppHandle, GlobalShortcutManager, Manager, State};
+
+use crate::{start_monitoring_internal, stop_monitoring_internal, AppState};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeyConfig {
+    pub toggle_monitoring: String,
+    pub show_window: String,
+    pub panic_stop: String,
+}
+
+impl Default for HotkeyConfig {
+    fn default() -> Self {
+        Self {
+            toggle_monitoring: "CommandOrControl+Shift+M".to_string(),
+            show_window: "CommandOrControl+Shift+S".to_string(),
+            panic_stop: "CommandOrControl+Shift+P".to_string(),
+        }
+    }
+}
+
+fn config_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app
+        .path_resolver()
+        .app_config_dir()
+        .ok_or("could not resolve app config directory")?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join("hotkeys.json"))
+}
+
+fn load_config(app: &AppHandle) -> HotkeyConfig {
+    config_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_config(app: &AppHandle, config: &HotkeyConfig) -> Result<(), String> {
+    let path = config_path(app)?;
+    let raw = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+    fs::write(path, raw).map_err(|e| e.to_string())
+}
+
+/// (Re-)registers the configured hotkeys, clearing any previously
+/// registered chords first. Returns the offending chord on failure (e.g.
+/// a malformed chord or one already taken by another application) rather
+/// than panicking, so callers can surface it to the user.
+pub fn register_hotkeys(app: &AppHandle) -> Result<(), String> {
+    let config = load_config(app);
+    apply_hotkeys(app, &config)
+}
+
+fn apply_hotkeys(app: &AppHandle, config: &HotkeyConfig) -> Result<(), String> {
+    let mut manager = app.global_shortcut_manager();
+    manager
+        .unregister_all()
+        .map_err(|e| format!("failed to clear existing hotkeys: {}", e))?;
+
+    let toggle_app = app.clone();
+    manager
+        .register(&config.toggle_monitoring, move || {
+            toggle_monitoring(&toggle_app);
+        })
+        .map_err(|e| format!("could not bind toggle monitoring chord \"{}\": {}", config.toggle_monitoring, e))?;
+
+    let show_app = app.clone();
+    manager
+        .register(&config.show_window, move || {
+            crate::show_main_window(&show_app);
+        })
+        .map_err(|e| format!("could not bind show window chord \"{}\": {}", config.show_window, e))?;
+
+    let panic_app = app.clone();
+    manager
+        .register(&config.panic_stop, move || {
+            stop_monitoring_internal(&panic_app.state::<AppState>());
+        })
+        .map_err(|e| format!("could not bind panic stop chord \"{}\": {}", config.panic_stop, e))?;
+
+    Ok(())
+}
+
+fn toggle_monitoring(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let is_monitoring = *state.is_monitoring.lock().unwrap();
+    if is_monitoring {
+        stop_monitoring_internal(&state);
+    } else {
+        start_monitoring_internal(app.clone(), &state);
+    }
+}
+
+#[tauri::command]
+pub async fn get_hotkeys(app: AppHandle) -> Result<HotkeyConfig, String> {
+    Ok(load_config(&app))
+}
+
+#[tauri::command]
+pub async fn set_hotkeys(
+    config: HotkeyConfig,
+    app: AppHandle,
+    _state: State<'_, AppState>,
+) -> Result<(), String> {
+    apply_hotkeys(&app, &config)?;
+    save_config(&app, &config)
+}
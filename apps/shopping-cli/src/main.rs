@@ -0,0 +1,123 @@
+//! Scriptable companion CLI for the Shopping Assistant desktop app. Talks to
+//! a running GUI instance over the same local IPC channel used internally,
+//! so `shopping-cli` never duplicates monitoring logic.
+
+use clap::{Parser, Subcommand};
+use shopping_ipc::{IpcRequest, IpcResponse};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+#[derive(Parser)]
+#[command(name = "shopping-cli", about = "Drive a running Shopping Assistant instance")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Start the monitoring loop
+    Start,
+    /// Stop the monitoring loop
+    Stop,
+    /// Print whether monitoring is currently active
+    Status,
+    /// List the user's watchers
+    Watchers {
+        /// Print the watcher list as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    let request = match &cli.command {
+        Command::Start => IpcRequest::StartMonitoring,
+        Command::Stop => IpcRequest::StopMonitoring,
+        Command::Status => IpcRequest::IsMonitoring,
+        Command::Watchers { .. } => IpcRequest::GetWatchers,
+    };
+
+    match send(request).await {
+        Ok(response) => print_response(&cli.command, response),
+        Err(err) => {
+            eprintln!("shopping-cli: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn send(request: IpcRequest) -> Result<IpcResponse, String> {
+    use tokio::net::UnixStream;
+
+    let path = shopping_ipc::socket_path();
+    let stream = UnixStream::connect(&path).await.map_err(|_| {
+        "could not reach the desktop app - is it running?".to_string()
+    })?;
+
+    let (read_half, mut write_half) = stream.into_split();
+    let mut line = serde_json::to_string(&request).map_err(|e| e.to_string())?;
+    line.push('\n');
+    write_half
+        .write_all(line.as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut reply = String::new();
+    BufReader::new(read_half)
+        .read_line(&mut reply)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    serde_json::from_str(reply.trim()).map_err(|e| e.to_string())
+}
+
+#[cfg(windows)]
+async fn send(request: IpcRequest) -> Result<IpcResponse, String> {
+    use tokio::net::windows::named_pipe::ClientOptions;
+
+    let pipe_name = shopping_ipc::pipe_name();
+    let client = ClientOptions::new().open(&pipe_name).map_err(|_| {
+        "could not reach the desktop app - is it running?".to_string()
+    })?;
+
+    let (read_half, mut write_half) = tokio::io::split(client);
+    let mut line = serde_json::to_string(&request).map_err(|e| e.to_string())?;
+    line.push('\n');
+    write_half
+        .write_all(line.as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut reply = String::new();
+    BufReader::new(read_half)
+        .read_line(&mut reply)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    serde_json::from_str(reply.trim()).map_err(|e| e.to_string())
+}
+
+fn print_response(command: &Command, response: IpcResponse) {
+    match (command, response) {
+        (_, IpcResponse::Error { message }) => {
+            eprintln!("error: {}", message);
+            std::process::exit(1);
+        }
+        (Command::Status, IpcResponse::Monitoring { active }) => {
+            println!("{}", if active { "monitoring" } else { "stopped" });
+        }
+        (Command::Watchers { json: true }, IpcResponse::Watchers { items }) => {
+            println!("{}", serde_json::to_string_pretty(&items).unwrap());
+        }
+        (Command::Watchers { json: false }, IpcResponse::Watchers { items }) => {
+            for watcher in items {
+                println!("{}\t{}\t{}", watcher.id, watcher.status, watcher.name);
+            }
+        }
+        _ => println!("ok"),
+    }
+}
@@ -0,0 +1,48 @@
+//! Request/response types shared between the desktop app's local IPC server
+//! and the `shopping-cli` companion binary, so the two stay in lockstep
+//! without either depending on the other.
+
+use serde::{Deserialize, Serialize};
+
+/// Name used to derive the platform-specific endpoint: a Unix domain socket
+/// under the temp dir on Linux/macOS, a named pipe on Windows.
+pub const IPC_ENDPOINT_NAME: &str = "shopping-assistant-ipc";
+
+#[cfg(unix)]
+pub fn socket_path() -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("{}.sock", IPC_ENDPOINT_NAME))
+}
+
+#[cfg(windows)]
+pub fn pipe_name() -> String {
+    format!(r"\\.\pipe\{}", IPC_ENDPOINT_NAME)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatcherDto {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub status: String,
+    pub last_check_at: Option<String>,
+}
+
+/// A command sent from `shopping-cli` to the running GUI, one JSON object
+/// per line on the IPC connection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum IpcRequest {
+    StartMonitoring,
+    StopMonitoring,
+    IsMonitoring,
+    GetWatchers,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum IpcResponse {
+    Ok,
+    Monitoring { active: bool },
+    Watchers { items: Vec<WatcherDto> },
+    Error { message: String },
+}